@@ -1,54 +1,231 @@
 use crate::io::{AsyncRead, AsyncWrite, ReadBuf};
 
 use std::future::Future;
-use std::io;
+use std::io::{self, IoSlice};
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+// A minimal single-slot waker, used to wake the task driving a `copy`
+// future from `CopyAbortHandle::abort`, which may be called from any thread.
+#[derive(Debug, Default)]
+struct AtomicWaker {
+    waker: Mutex<Option<Waker>>,
+}
+
+impl AtomicWaker {
+    fn register(&self, waker: &Waker) {
+        *self.waker.lock().unwrap() = Some(waker.clone()); //每次poll都刷新一下waker，保证abort能唤醒到最新的task
+    }
+
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct AbortState {
+    aborted: AtomicBool,
+    waker: AtomicWaker,
+}
+
+/// A handle that can be used to cancel an in-progress [`copy_abortable`] operation.
+///
+/// Cloning a `CopyAbortHandle` gives another handle to the same underlying
+/// copy; calling [`abort`][CopyAbortHandle::abort] on any clone stops the copy.
+#[derive(Debug, Clone)]
+pub struct CopyAbortHandle(Arc<AbortState>);
+
+impl CopyAbortHandle {
+    /// Signals the associated [`copy_abortable`] future to stop as soon as
+    /// possible.
+    ///
+    /// The copy does not stop immediately: any bytes already read into the
+    /// internal buffer are still written out and the writer is flushed
+    /// before the future resolves, so the reported byte count is accurate
+    /// and the writer is left in a consistent state.
+    pub fn abort(&self) {
+        self.0.aborted.store(true, Ordering::Release);
+        self.0.waker.wake();
+    }
+}
+
+/// The outcome of a [`copy_abortable`] operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyOutcome {
+    /// The reader reached EOF and all buffered data was flushed to the
+    /// writer. The value is the total number of bytes copied.
+    Done(u64),
+    /// The copy was stopped via [`CopyAbortHandle::abort`] before the reader
+    /// reached EOF. The value is the number of bytes copied before the abort
+    /// took effect.
+    Aborted(u64),
+}
 
 #[derive(Debug)]
-pub(super) struct CopyBuffer {
-    read_done: bool,
-    need_flush: bool,
+struct Segment {
+    buf: Box<[u8]>,
     pos: usize,
     cap: usize,
+}
+
+impl Segment {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.cap
+    }
+
+    fn has_room(&self) -> bool {
+        self.cap < self.buf.len()
+    }
+
+    fn unwritten(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+
+    fn reset(&mut self) {
+        self.pos = 0;
+        self.cap = 0;
+    }
+}
+
+// Two segments are enough to let the reader fill one while the writer
+// drains the other; a larger ring buys little extra overlap in practice and
+// costs more memory and more `IoSlice`s per vectored write.
+const SEGMENTS: usize = 2;
+
+pub(super) struct CopyBuffer<'a> {
+    read_done: bool,
+    need_flush: bool,
     amt: u64,
-    buf: Box<[u8]>,
+    segments: [Segment; SEGMENTS],
+    // The segment the reader is currently targeting.
+    fill_idx: usize,
+    // The segment at the head of the write queue.
+    write_idx: usize,
+    abort: Option<Arc<AbortState>>,
+    aborted: bool,
+    progress: Option<Box<dyn FnMut(u64) + 'a>>,
+}
+
+impl std::fmt::Debug for CopyBuffer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CopyBuffer")
+            .field("read_done", &self.read_done)
+            .field("need_flush", &self.need_flush)
+            .field("amt", &self.amt)
+            .field("segments", &self.segments)
+            .field("fill_idx", &self.fill_idx)
+            .field("write_idx", &self.write_idx)
+            .field("abort", &self.abort)
+            .field("aborted", &self.aborted)
+            .field("progress", &self.progress.as_ref().map(|_| "Fn"))
+            .finish()
+    }
 }
 
-impl CopyBuffer {
+impl<'a> CopyBuffer<'a> {
     pub(super) fn new() -> Self {
+        Self::with_capacity(super::DEFAULT_BUF_SIZE)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
         Self {
             read_done: false,
             need_flush: false,
-            pos: 0,
-            cap: 0,
             amt: 0,
-            buf: vec![0; super::DEFAULT_BUF_SIZE].into_boxed_slice(), //默认构建一个8k的buffer，默认放在堆上
+            segments: [
+                Segment::with_capacity(capacity),
+                Segment::with_capacity(capacity),
+            ], //两个互相独立的segment，一个在被写出的时候，另一个可以继续被读取填充
+            fill_idx: 0,
+            write_idx: 0,
+            abort: None,
+            aborted: false,
+            progress: None,
+        }
+    }
+
+    fn new_abortable(abort: Arc<AbortState>) -> Self {
+        Self {
+            abort: Some(abort),
+            ..Self::new()
         }
     }
 
-    fn poll_fill_buf<R>(
+    fn new_with_progress(progress: Box<dyn FnMut(u64) + 'a>) -> Self {
+        Self {
+            progress: Some(progress),
+            ..Self::new()
+        }
+    }
+
+    // Seeds the first segment with data the caller already read from the
+    // reader (e.g. while peeking at a protocol header), so it is written out
+    // ahead of any data read afterwards. `fill_idx` only hands off to the
+    // other segment once this one is full (`has_room()` returns false), so
+    // freshly-read bytes are appended right after the seed data in the same
+    // segment until it fills up - the other segment stays untouched until
+    // then. `write_idx` starts on this segment either way, so its bytes
+    // always reach the writer first. Grows the segment if the seed data
+    // doesn't fit.
+    fn with_initial_data(mut self, initial_data: &[u8]) -> Self {
+        let seg = &mut self.segments[0];
+        if initial_data.len() > seg.buf.len() {
+            seg.buf = vec![0; initial_data.len()].into_boxed_slice();
+        }
+        seg.buf[..initial_data.len()].copy_from_slice(initial_data);
+        seg.cap = initial_data.len();
+        self
+    }
+
+    fn poll_fill_segment<R>(
         &mut self,
         cx: &mut Context<'_>,
         reader: Pin<&mut R>,
+        idx: usize,
     ) -> Poll<io::Result<()>>
     where
         R: AsyncRead + ?Sized,
     {
-        let me = &mut *self;
-        let mut buf = ReadBuf::new(&mut me.buf); //构建reader buffer
-        buf.set_filled(me.cap); // 双位置指针的buffer，pos记录数据在开始位置，cap记录数据结束位置
-
-        let res = reader.poll_read(cx, &mut buf); //让reader填充buffer
-        if let Poll::Ready(Ok(())) = res { //填充成功
-            let filled_len = buf.filled().len(); //得到buffer已经填充了多少
-            me.read_done = me.cap == filled_len; //如果填充的数量和自身的容量大小相同，说明reader已经完成了工作，因为本次接收的数据为0
-            me.cap = filled_len; //我们当前填充了多少
+        let cap = self.segments[idx].cap;
+        let mut buf = ReadBuf::new(&mut self.segments[idx].buf);
+        buf.set_filled(cap);
+
+        let res = reader.poll_read(cx, &mut buf);
+        if let Poll::Ready(Ok(())) = res {
+            let filled_len = buf.filled().len();
+            self.read_done = cap == filled_len; //本次没有读到任何新数据，说明reader已经结束
+            self.segments[idx].cap = filled_len;
         }
         res
     }
 
-    fn poll_write_buf<R, W>(
+    // Gives the reader a chance to make progress on the segment it's
+    // currently targeting while the writer is stalled, mirroring the
+    // "top up on pending" behavior of the single-buffer implementation.
+    fn poll_top_up<R>(&mut self, cx: &mut Context<'_>, reader: Pin<&mut R>) -> Poll<io::Result<()>>
+    where
+        R: AsyncRead + ?Sized,
+    {
+        if !self.read_done && !self.aborted && self.segments[self.fill_idx].has_room() {
+            self.poll_fill_segment(cx, reader, self.fill_idx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn poll_write_segments<R, W>(
         &mut self,
         cx: &mut Context<'_>,
         mut reader: Pin<&mut R>,
@@ -58,17 +235,71 @@ impl CopyBuffer {
         R: AsyncRead + ?Sized,
         W: AsyncWrite + ?Sized,
     {
-        let me = &mut *self;
-        match writer.as_mut().poll_write(cx, &me.buf[me.pos..me.cap]) {
+        let first = self.write_idx;
+        let second = first ^ 1;
+        // Only fold the other segment into the same syscall once it's
+        // settled data the reader isn't actively appending to right now.
+        let include_second = second != self.fill_idx && !self.segments[second].is_empty();
+
+        let vectored = writer.is_write_vectored();
+        // Whether `second`'s bytes were actually handed to this specific
+        // write call - only true on the vectored path when `include_second`
+        // folded it in. The non-vectored branch only ever passes
+        // `segments[first]`, even if `second` currently holds real unwritten
+        // data (e.g. filled via `poll_top_up` while this write was pending).
+        let wrote_second = vectored && include_second;
+
+        let res = if vectored {
+            let slices = [
+                IoSlice::new(self.segments[first].unwritten()),
+                IoSlice::new(self.segments[second].unwritten()),
+            ];
+            let n = if include_second { 2 } else { 1 };
+            writer.as_mut().poll_write_vectored(cx, &slices[..n])
+        } else {
+            writer.as_mut().poll_write(cx, self.segments[first].unwritten())
+        };
+
+        match res {
             Poll::Pending => {
-                // Top up the buffer towards full if we can read a bit more
-                // data - this should improve the chances of a large write
-                if !me.read_done && me.cap < me.buf.len() {
-                    ready!(me.poll_fill_buf(cx, reader.as_mut()))?;
-                }
+                // The writer has no room right now - see if the reader can
+                // make progress on the segment it's filling instead.
+                ready!(self.poll_top_up(cx, reader.as_mut()))?;
                 Poll::Pending
             }
-            res => res,
+            Poll::Ready(Ok(n)) => {
+                let first_len = self.segments[first].unwritten().len();
+                // Bound against only the bytes actually passed to the
+                // writer in this call - if `second` wasn't included, it may
+                // still hold real unwritten data from a concurrent fill, and
+                // using its length here would let an overreport silently
+                // swallow those bytes instead of being caught below.
+                let second_len = if wrote_second {
+                    self.segments[second].unwritten().len()
+                } else {
+                    0
+                };
+                if n > first_len + second_len {
+                    // The writer reported writing more bytes than it was
+                    // given across both `IoSlice`s - the same class of bug
+                    // the single-buffer implementation caught with
+                    // `debug_assert!(self.pos <= self.cap, ...)`. Surface it
+                    // as an error in every build profile instead of letting
+                    // `pos` run past `cap` and panic on the next `unwritten()`.
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "writer reported writing more bytes than were provided",
+                    )));
+                }
+                let on_first = n.min(first_len);
+                self.segments[first].pos += on_first;
+                let on_second = (n - on_first).min(second_len);
+                if on_second > 0 {
+                    self.segments[second].pos += on_second;
+                }
+                Poll::Ready(Ok(n))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
         }
     }
 
@@ -95,14 +326,21 @@ impl CopyBuffer {
         ))]
         // Keep track of task budget
         let coop = ready!(crate::runtime::coop::poll_proceed(cx));
-        loop {
-            // If our buffer is empty, then we need to read some data to
-            // continue.
-            if self.pos == self.cap && !self.read_done { //读取没有关闭，并且我们的数据已经发送完成了
-                self.pos = 0;
-                self.cap = 0;
 
-                match self.poll_fill_buf(cx, reader.as_mut()) {
+        if let Some(abort) = &self.abort {
+            // Re-register on every poll so `abort()` can wake us no matter
+            // which task last polled this future.
+            abort.waker.register(cx.waker());
+            if !self.aborted && !self.read_done && abort.aborted.load(Ordering::Acquire) {
+                self.aborted = true; //一旦观察到abort标记，就不再读取新的数据，只把已经读到的数据写出去
+            }
+        }
+
+        loop {
+            // Keep the segment the reader is currently targeting topped up.
+            let mut fill_pending = false;
+            if !self.read_done && !self.aborted && self.segments[self.fill_idx].has_room() {
+                match self.poll_fill_segment(cx, reader.as_mut(), self.fill_idx) {
                     Poll::Ready(Ok(())) => {
                         #[cfg(any(
                             feature = "fs",
@@ -115,7 +353,7 @@ impl CopyBuffer {
                             feature = "time",
                         ))]
                         coop.made_progress();
-                    } //填充buffer，推进进度
+                    }
                     Poll::Ready(Err(err)) => {
                         #[cfg(any(
                             feature = "fs",
@@ -130,33 +368,41 @@ impl CopyBuffer {
                         coop.made_progress();
                         return Poll::Ready(Err(err));
                     }
-                    Poll::Pending => {
-                        // Try flushing when the reader has no progress to avoid deadlock
-                        // when the reader depends on buffered writer.
-                        if self.need_flush {
-                            ready!(writer.as_mut().poll_flush(cx))?;
-                            #[cfg(any(
-                                feature = "fs",
-                                feature = "io-std",
-                                feature = "net",
-                                feature = "process",
-                                feature = "rt",
-                                feature = "signal",
-                                feature = "sync",
-                                feature = "time",
-                            ))]
-                            coop.made_progress();
-                            self.need_flush = false;
-                        } //强制的flush writer
-
-                        return Poll::Pending;
-                    }
+                    Poll::Pending => fill_pending = true,
+                }
+            }
+
+            // Once the segment being filled is full (or we're at EOF/aborted)
+            // and the other segment is free, hand filling duty over to it so
+            // the reader can get ahead of the writer.
+            if self.fill_idx == self.write_idx
+                && (self.read_done || self.aborted || !self.segments[self.fill_idx].has_room())
+            {
+                let other = self.fill_idx ^ 1;
+                if self.segments[other].is_empty() {
+                    self.fill_idx = other;
                 }
             }
 
-            // If our buffer has some data, let's write it out!
-            while self.pos < self.cap {
-                let i = ready!(self.poll_write_buf(cx, reader.as_mut(), writer.as_mut()))?; //进行writer的输出
+            // Drain whatever has accumulated, advancing to the next segment
+            // once the current one empties.
+            loop {
+                if self.segments[self.write_idx].is_empty() {
+                    let other = self.write_idx ^ 1;
+                    if self.segments[other].is_empty() {
+                        break;
+                    }
+                    self.segments[self.write_idx].reset();
+                    self.write_idx = other;
+                    // Keep the reader one step ahead of the writer.
+                    if self.fill_idx == self.write_idx && !self.read_done && !self.aborted {
+                        self.fill_idx = self.write_idx ^ 1;
+                    }
+                    continue;
+                }
+
+                let i =
+                    ready!(self.poll_write_segments(cx, reader.as_mut(), writer.as_mut()))?; //进行writer的输出，可能是向量化写
                 #[cfg(any(
                     feature = "fs",
                     feature = "io-std",
@@ -168,29 +414,23 @@ impl CopyBuffer {
                     feature = "time",
                 ))]
                 coop.made_progress();
-                if i == 0 { //写出错了，对面有可能关闭的pipe
+                if i == 0 {
+                    //写出错了，对面有可能关闭的pipe
                     return Poll::Ready(Err(io::Error::new(
                         io::ErrorKind::WriteZero,
                         "write zero byte into writer",
                     )));
                 } else {
-                    self.pos += i; //调整指针位置
                     self.amt += i as u64; //增加吞吐量
                     self.need_flush = true; //标记需要flush
+                    if let Some(progress) = &mut self.progress {
+                        progress(self.amt);
+                    }
                 }
             }
 
-            // If pos larger than cap, this loop will never stop.
-            // In particular, user's wrong poll_write implementation returning
-            // incorrect written length may lead to thread blocking.
-            debug_assert!(
-                self.pos <= self.cap,
-                "writer returned length larger than input slice"
-            );
-
-            // If we've written all the data and we've seen EOF, flush out the
-            // data and finish the transfer.
-            if self.pos == self.cap && self.read_done {
+            // Reaching here means both segments are empty.
+            if self.read_done || self.aborted {
                 ready!(writer.as_mut().poll_flush(cx))?; //刷写出端
                 #[cfg(any(
                     feature = "fs",
@@ -203,8 +443,34 @@ impl CopyBuffer {
                     feature = "time",
                 ))]
                 coop.made_progress();
+                if let Some(progress) = &mut self.progress {
+                    // Fire a final update now that the flush has completed,
+                    // right before resolving with the total.
+                    progress(self.amt);
+                }
                 return Poll::Ready(Ok(self.amt)); //返回总共传递了多少数据
             }
+
+            if fill_pending {
+                // Try flushing when the reader has no progress to avoid deadlock
+                // when the reader depends on buffered writer.
+                if self.need_flush {
+                    ready!(writer.as_mut().poll_flush(cx))?;
+                    #[cfg(any(
+                        feature = "fs",
+                        feature = "io-std",
+                        feature = "net",
+                        feature = "process",
+                        feature = "rt",
+                        feature = "signal",
+                        feature = "sync",
+                        feature = "time",
+                    ))]
+                    coop.made_progress();
+                    self.need_flush = false;
+                }
+                return Poll::Pending;
+            }
         }
     }
 }
@@ -216,7 +482,7 @@ impl CopyBuffer {
 struct Copy<'a, R: ?Sized, W: ?Sized> {
     reader: &'a mut R,
     writer: &'a mut W,
-    buf: CopyBuffer,
+    buf: CopyBuffer<'a>,
 }
 
 cfg_io_util! {
@@ -274,6 +540,135 @@ cfg_io_util! {
     }
 }
 
+cfg_io_util! {
+    /// Asynchronously copies the entire contents of a reader into a writer,
+    /// invoking a callback with the cumulative byte count as data is written.
+    ///
+    /// This is the same as [`copy`], except `progress` is called with the
+    /// running total every time a write to `writer` makes progress,
+    /// and once more after the final flush completes, right before the
+    /// copy resolves. This lets callers
+    /// drive a progress bar or rate limiter without wrapping the reader or
+    /// writer themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::io;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let mut reader: &[u8] = b"hello";
+    /// let mut writer: Vec<u8> = vec![];
+    /// let mut last = 0;
+    ///
+    /// io::copy_with_progress(&mut reader, &mut writer, |amt| last = amt).await?;
+    ///
+    /// assert_eq!(last, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_with_progress<'a, R, W, F>(
+        reader: &'a mut R,
+        writer: &'a mut W,
+        progress: F,
+    ) -> io::Result<u64>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+        F: FnMut(u64) + 'a,
+    {
+        Copy {
+            reader,
+            writer,
+            buf: CopyBuffer::new_with_progress(Box::new(progress)),
+        }
+        .await
+    }
+}
+
+cfg_io_util! {
+    /// Builds an [`io::copy`](super::copy) operation with a configurable
+    /// buffer, instead of the fixed 8 KB default.
+    ///
+    /// This is useful for high-throughput pipes where a larger buffer
+    /// reduces the number of read/write syscalls, without the caller having
+    /// to reach for [`copy_buf`](super::copy_buf) plus a [`BufReader`].
+    ///
+    /// [`BufReader`]: crate::io::BufReader
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::io::CopyBuilder;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let mut reader: &[u8] = b"hello";
+    /// let mut writer: Vec<u8> = vec![];
+    ///
+    /// CopyBuilder::new()
+    ///     .with_capacity(256 * 1024)
+    ///     .copy(&mut reader, &mut writer)
+    ///     .await?;
+    ///
+    /// assert_eq!(&b"hello"[..], &writer[..]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct CopyBuilder {
+        capacity: usize,
+        initial_data: Vec<u8>,
+    }
+
+    impl CopyBuilder {
+        /// Creates a new `CopyBuilder` with the default 8 KB buffer capacity.
+        pub fn new() -> Self {
+            Self {
+                capacity: super::DEFAULT_BUF_SIZE,
+                initial_data: Vec::new(),
+            }
+        }
+
+        /// Sets the capacity of each of the copy's internal buffer segments.
+        pub fn with_capacity(mut self, capacity: usize) -> Self {
+            self.capacity = capacity;
+            self
+        }
+
+        /// Seeds the copy buffer with data that has already been read from
+        /// the reader, so that it is written out ahead of any data read
+        /// afterwards. Freshly-read bytes are appended right after the seed
+        /// data and drained together with it; the reader isn't given a
+        /// second, concurrently-filled segment to get ahead on until this
+        /// one fills up. The buffer capacity is grown to fit `initial_data`
+        /// if necessary.
+        pub fn with_initial_data(mut self, initial_data: &[u8]) -> Self {
+            self.initial_data = initial_data.to_vec();
+            self
+        }
+
+        /// Copies data from `reader` into `writer` using this builder's
+        /// configuration.
+        ///
+        /// See [`copy`](super::copy) for details on the behavior of the
+        /// copy itself.
+        pub async fn copy<R, W>(self, reader: &mut R, writer: &mut W) -> io::Result<u64>
+        where
+            R: AsyncRead + Unpin + ?Sized,
+            W: AsyncWrite + Unpin + ?Sized,
+        {
+            let buf = CopyBuffer::with_capacity(self.capacity).with_initial_data(&self.initial_data);
+            Copy { reader, writer, buf }.await
+        }
+    }
+
+    impl Default for CopyBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
 impl<R, W> Future for Copy<'_, R, W>
 where
     R: AsyncRead + Unpin + ?Sized,
@@ -288,3 +683,88 @@ where
             .poll_copy(cx, Pin::new(&mut *me.reader), Pin::new(&mut *me.writer))
     }
 }
+
+/// A future that asynchronously copies the entire contents of a reader into a
+/// writer, and that can be stopped early via a [`CopyAbortHandle`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+struct CopyAbortable<'a, R: ?Sized, W: ?Sized> {
+    reader: &'a mut R,
+    writer: &'a mut W,
+    buf: CopyBuffer<'a>,
+}
+
+cfg_io_util! {
+    /// Asynchronously copies the entire contents of a reader into a writer,
+    /// returning a handle that can be used to stop the copy early.
+    ///
+    /// This is the same as [`copy`], except it returns a
+    /// [`CopyAbortHandle`] alongside the future. Calling
+    /// [`CopyAbortHandle::abort`] tells the copy to stop reading as soon as
+    /// possible; any data already buffered is still written out and the
+    /// writer is flushed before the future resolves, so the returned
+    /// [`CopyOutcome::Aborted`] count is accurate.
+    ///
+    /// Simply dropping the future also stops the copy, but throws away the
+    /// running byte count - `copy_abortable` is for callers that need to
+    /// know how far the transfer got, e.g. to resume it later or to log it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::io::{self, CopyOutcome};
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let mut reader: &[u8] = b"hello world";
+    /// let mut writer: Vec<u8> = vec![];
+    ///
+    /// let (copy, handle) = io::copy_abortable(&mut reader, &mut writer);
+    /// // Aborting before the future is ever polled means it never gets a
+    /// // chance to read anything.
+    /// handle.abort();
+    /// let outcome = copy.await?;
+    /// assert_eq!(outcome, CopyOutcome::Aborted(0));
+    /// assert!(writer.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_abortable<'a, R, W>(
+        reader: &'a mut R,
+        writer: &'a mut W,
+    ) -> (impl Future<Output = io::Result<CopyOutcome>> + 'a, CopyAbortHandle)
+    where
+        R: AsyncRead + Unpin + ?Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let abort = Arc::new(AbortState::default());
+        let handle = CopyAbortHandle(Arc::clone(&abort));
+        let future = CopyAbortable {
+            reader,
+            writer,
+            buf: CopyBuffer::new_abortable(abort),
+        };
+        (future, handle)
+    }
+}
+
+impl<R, W> Future for CopyAbortable<'_, R, W>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    type Output = io::Result<CopyOutcome>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = &mut *self;
+
+        let amt = ready!(me
+            .buf
+            .poll_copy(cx, Pin::new(&mut *me.reader), Pin::new(&mut *me.writer)))?;
+
+        Poll::Ready(Ok(if me.buf.aborted {
+            CopyOutcome::Aborted(amt)
+        } else {
+            CopyOutcome::Done(amt)
+        }))
+    }
+}
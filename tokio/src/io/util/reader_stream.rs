@@ -0,0 +1,158 @@
+use crate::io::AsyncRead;
+
+use bytes::{Bytes, BytesMut};
+use futures_core::Stream;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+// The default capacity a fresh `ReaderStream` allocates its internal buffer
+// with; chosen to match `DEFAULT_BUF_SIZE` so a `ReaderStream` and a plain
+// `copy` pull data from the underlying reader in similarly sized chunks.
+const DEFAULT_CAPACITY: usize = super::DEFAULT_BUF_SIZE;
+
+cfg_io_util! {
+    /// Converts an [`AsyncRead`] into a [`Stream`] of byte chunks.
+    ///
+    /// This stream is fused: once it returns `None`, or an `Err`, all future
+    /// calls to [`poll_next`](Stream::poll_next) will return `None`.
+    ///
+    /// Each poll reads as much as the reader makes available right now into
+    /// an internal buffer and, on success, hands back whatever was filled as
+    /// a single [`Bytes`] chunk, the same "read whatever is ready" behavior
+    /// [`CopyBuffer`](super::copy::CopyBuffer) uses to fill its own segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tokio_stream::StreamExt;
+    /// use tokio::io::ReaderStream;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let reader: &[u8] = b"hello";
+    /// let mut stream = ReaderStream::new(reader);
+    ///
+    /// let mut collected = Vec::new();
+    /// while let Some(chunk) = stream.next().await {
+    ///     collected.extend_from_slice(&chunk?);
+    /// }
+    /// assert_eq!(collected, b"hello");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct ReaderStream<R> {
+        reader: R,
+        // The buffer we read into. Each yielded chunk takes ownership of
+        // the bytes read into it via `split`, leaving the unfilled spare
+        // capacity behind for the next read; `capacity` more bytes are
+        // reserved only once that spare capacity is used up.
+        buf: BytesMut,
+        capacity: usize,
+        // Set once the reader has reported EOF or an error, so we stay
+        // fused instead of polling a reader that already finished.
+        done: bool,
+    }
+}
+
+impl<R: AsyncRead> ReaderStream<R> {
+    /// Creates a new `ReaderStream` with the default capacity.
+    pub fn new(reader: R) -> Self {
+        ReaderStream {
+            reader,
+            buf: BytesMut::new(),
+            capacity: DEFAULT_CAPACITY,
+            done: false,
+        }
+    }
+
+    /// Creates a new `ReaderStream` whose internal buffer starts with room
+    /// for `capacity` bytes.
+    pub fn with_capacity(reader: R, capacity: usize) -> Self {
+        ReaderStream {
+            reader,
+            buf: BytesMut::with_capacity(capacity),
+            capacity,
+            done: false,
+        }
+    }
+
+    /// Consumes this `ReaderStream`, returning the underlying reader.
+    ///
+    /// Any bytes already read into the internal buffer but not yet yielded
+    /// as a chunk are discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for ReaderStream<R> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+
+        if me.done {
+            return Poll::Ready(None);
+        }
+
+        if me.buf.capacity() == 0 {
+            me.buf.reserve(me.capacity);
+        }
+
+        match poll_fill_buf(Pin::new(&mut me.reader), cx, &mut me.buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(err)) => {
+                me.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+            Poll::Ready(Ok(0)) => {
+                me.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Ok(_)) => {
+                let chunk = me.buf.split();
+                Poll::Ready(Some(Ok(chunk.freeze())))
+            }
+        }
+    }
+}
+
+// Reads as much as `reader` makes available right now into the spare
+// capacity of `buf`, advancing `buf`'s length by however much was filled.
+fn poll_fill_buf<R: AsyncRead + ?Sized>(
+    reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    buf: &mut BytesMut,
+) -> Poll<io::Result<usize>> {
+    use crate::io::ReadBuf;
+    use bytes::BufMut;
+
+    if !buf.has_remaining_mut() {
+        return Poll::Ready(Ok(0));
+    }
+
+    let n = {
+        let dst = buf.chunk_mut();
+        // Safety: `ReadBuf::uninit` only ever writes into the slice it is
+        // given and exposes the initialized prefix through `filled()`, so
+        // treating the uninitialized spare capacity as `MaybeUninit<u8>` for
+        // the duration of this call is sound.
+        let dst = unsafe { &mut *(dst as *mut _ as *mut [std::mem::MaybeUninit<u8>]) };
+        let mut read_buf = ReadBuf::uninit(dst);
+        let ptr = read_buf.filled().as_ptr();
+        ready!(reader.poll_read(cx, &mut read_buf))?;
+
+        // `poll_read` must only append to the filled portion of the buffer
+        // we handed it, never move or shrink it.
+        assert_eq!(read_buf.filled().as_ptr(), ptr);
+        read_buf.filled().len()
+    };
+
+    // Safety: `poll_read` reported `n` bytes as filled above.
+    unsafe {
+        buf.advance_mut(n);
+    }
+
+    Poll::Ready(Ok(n))
+}
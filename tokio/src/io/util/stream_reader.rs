@@ -0,0 +1,160 @@
+use crate::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use bytes::Buf;
+use futures_core::Stream;
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+cfg_io_util! {
+    /// Turns a [`Stream`] of byte chunks into an [`AsyncRead`].
+    ///
+    /// This is the inverse of [`ReaderStream`](super::ReaderStream): it holds
+    /// on to whatever chunk the stream last produced and hands out bytes
+    /// from it across calls to `poll_read`, asking the stream for the next
+    /// chunk only once the current one is fully consumed. A chunk is any
+    /// type implementing [`Buf`], so callers that already have `Bytes` (or
+    /// anything else `Buf`) on hand don't need to copy them into a fresh
+    /// buffer up front.
+    ///
+    /// If the stream yields an `Err`, it is returned to the caller exactly
+    /// once and the `StreamReader` will return `Ok(())` with no bytes read
+    /// (EOF) on every subsequent call - any bytes buffered from a prior
+    /// chunk before the error are not lost, since the error is only
+    /// surfaced after they've been drained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use std::io;
+    /// use tokio::io::{AsyncReadExt, StreamReader};
+    /// use tokio_stream::iter;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let chunks: Vec<io::Result<Bytes>> = vec![Ok(Bytes::from_static(b"hello "))];
+    /// let mut reader = StreamReader::new(iter(chunks));
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf).await?;
+    /// assert_eq!(buf, b"hello ");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct StreamReader<S, B> {
+        // The stream we're reading chunks from.
+        stream: S,
+        // The current chunk, with `Buf::advance` tracking how much of it has
+        // already been copied out via `poll_read`.
+        chunk: Option<B>,
+        // Set once the stream has yielded `None` or an `Err`, so we stop
+        // polling it and keep returning EOF afterwards instead of polling a
+        // stream that already finished.
+        done: bool,
+    }
+}
+
+impl<S, B, E> StreamReader<S, B>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: Buf,
+    E: Into<io::Error>,
+{
+    /// Creates a new `StreamReader` with no chunk buffered yet.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            chunk: None,
+            done: false,
+        }
+    }
+
+    /// Consumes this `StreamReader`, returning the underlying stream.
+    ///
+    /// Any bytes remaining in the currently buffered chunk are discarded.
+    /// Note that if the current chunk is only partially consumed, e.g. if
+    /// this reader is used with a `BufReader`, those bytes are lost.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, B, E> AsyncRead for StreamReader<S, B>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf + Unpin,
+    E: Into<io::Error>,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let inner_buf = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(buf)) => buf,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let len = cmp::min(inner_buf.len(), buf.remaining());
+        buf.put_slice(&inner_buf[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S, B, E> AsyncBufRead for StreamReader<S, B>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf + Unpin,
+    E: Into<io::Error>,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let me = self.get_mut();
+
+        loop {
+            if let Some(chunk) = &me.chunk {
+                if chunk.has_remaining() {
+                    break;
+                }
+                me.chunk = None;
+            }
+
+            if me.done {
+                return Poll::Ready(Ok(&[]));
+            }
+
+            match Pin::new(&mut me.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    // Loop back around to check whether the new chunk is
+                    // non-empty before returning it.
+                    me.chunk = Some(chunk);
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    me.done = true;
+                    return Poll::Ready(Err(err.into()));
+                }
+                Poll::Ready(None) => {
+                    me.done = true;
+                    return Poll::Ready(Ok(&[]));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(me.chunk.as_ref().unwrap().chunk()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        let me = self.get_mut();
+        if amount == 0 {
+            return;
+        }
+        me.chunk
+            .as_mut()
+            .expect("consume called with no chunk buffered")
+            .advance(amount);
+    }
+}
@@ -0,0 +1,314 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use tokio::io::{self, AsyncRead, AsyncWrite, CopyOutcome, ReadBuf};
+use tokio_test::io::Builder;
+use tokio_test::{assert_pending, assert_ready};
+
+// A reader that hands back `first`, then suspends for exactly one poll
+// before it would hand back `second`. This opens a window, between the two
+// `poll` calls a test drives by hand, during which `CopyAbortHandle::abort`
+// can be called and observed to actually stop the copy before `second` is
+// ever read.
+struct PendOnceThenChunk {
+    first: Option<&'static [u8]>,
+    second: Option<&'static [u8]>,
+    pended: bool,
+}
+
+impl AsyncRead for PendOnceThenChunk {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if let Some(chunk) = self.first.take() {
+            buf.put_slice(chunk);
+            return Poll::Ready(Ok(()));
+        }
+        if !self.pended {
+            self.pended = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        if let Some(chunk) = self.second.take() {
+            buf.put_slice(chunk);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn copy_abortable_stops_reading_after_abort() {
+    let mut reader = PendOnceThenChunk {
+        first: Some(b"hello"),
+        second: Some(b"world"),
+        pended: false,
+    };
+    let mut writer = Builder::new().write(b"hello").build();
+
+    let (copy, handle) = io::copy_abortable(&mut reader, &mut writer);
+    let mut t = tokio_test::task::spawn(copy);
+
+    // The first chunk is read and written, then the reader suspends right
+    // before the second chunk - exactly the window `abort` is meant for.
+    assert_pending!(t.poll());
+
+    handle.abort();
+    let outcome = assert_ready!(t.poll()).unwrap();
+
+    // Only the bytes read (and written) before the abort took effect are
+    // reported; the second chunk must never be read.
+    assert_eq!(outcome, CopyOutcome::Aborted(5));
+}
+
+#[tokio::test]
+async fn copy_abortable_reports_done_if_abort_is_too_late() {
+    let mut reader: &[u8] = b"hello";
+    let mut writer = Builder::new().write(b"hello").build();
+
+    let (copy, handle) = io::copy_abortable(&mut reader, &mut writer);
+    let outcome = copy.await.unwrap();
+
+    // Aborting after the copy has already resolved is a no-op.
+    handle.abort();
+    assert_eq!(outcome, CopyOutcome::Done(5));
+}
+
+// A writer whose `poll_write`/`poll_write_vectored` always claims to have
+// written one more byte than it was actually given - the misbehavior
+// `CopyBuffer::poll_write_segments` must reject with an error instead of
+// letting a segment's `pos` run past its `cap`.
+struct OverreportingWriter;
+
+impl AsyncWrite for OverreportingWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Ok(buf.len() + 1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn copy_errors_instead_of_panicking_on_overreporting_writer() {
+    let mut reader: &[u8] = b"hello world";
+    let mut writer = OverreportingWriter;
+
+    // Without the overreport check, this would eventually panic inside `unwritten()`
+    // (`pos > cap`) instead of surfacing a normal `io::Result::Err`.
+    let result = io::copy(&mut reader, &mut writer).await;
+    assert!(result.is_err());
+}
+
+// A writer that reports vectored-write support and records every call's
+// slices, so a double-buffered copy's output can be checked for byte-exact
+// ordering however the segments get shuffled internally.
+#[derive(Default)]
+struct RecordingVectoredWriter {
+    written: Arc<Mutex<Vec<u8>>>,
+}
+
+impl AsyncWrite for RecordingVectoredWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_write_vectored(cx, &[IoSlice::new(buf)])
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let mut guard = self.written.lock().unwrap();
+        let mut total = 0;
+        for buf in bufs {
+            guard.extend_from_slice(buf);
+            total += buf.len();
+        }
+        Poll::Ready(Ok(total))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+// A reader that hands back fixed chunks on successive calls, then reports
+// EOF (an unchanged, still-short `buf`) forever after - used to pin down
+// exactly which segment gets filled with how many bytes on each
+// `poll_read`, so a test can put the double buffer into a specific
+// partially-filled state.
+struct ChunkThenEof {
+    chunks: Vec<&'static [u8]>,
+    next: usize,
+}
+
+impl AsyncRead for ChunkThenEof {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if let Some(chunk) = self.chunks.get(self.next) {
+            self.next += 1;
+            buf.put_slice(chunk);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+// A writer whose first `poll_write` is `Pending` (opening a window for the
+// reader to top up the other segment) and whose second call overreports by
+// one byte, like `OverreportingWriter` above, but only ever receives
+// `segments[first]` - i.e. it never claims vectored support, so
+// `poll_write_segments` takes the plain `poll_write` path.
+struct PendThenOverreport {
+    calls: u32,
+}
+
+impl AsyncWrite for PendThenOverreport {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.calls += 1;
+        if self.calls == 1 {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(buf.len() + 1))
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn copy_errors_instead_of_stealing_unwritten_bytes_from_concurrently_filled_segment() {
+    // Capacity 4 so the first chunk fills segment 0 exactly, handing fill
+    // duty to segment 1 for the second chunk.
+    let mut reader = ChunkThenEof {
+        chunks: vec![b"AAAA", b"BB"],
+        next: 0,
+    };
+    let mut writer = PendThenOverreport { calls: 0 };
+
+    // Before this fix, the overreported byte from the write of segment 0
+    // alone would be silently charged against segment 1's still-unwritten
+    // "BB" (real data the writer was never given, since it doesn't support
+    // vectored writes) instead of being caught as an error.
+    let result = io::CopyBuilder::new()
+        .with_capacity(4)
+        .copy(&mut reader, &mut writer)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn copy_with_tiny_buffer_preserves_byte_order_through_vectored_writes() {
+    let data = b"AAAABBBBCCCCDDDDEEEEFFFF".to_vec();
+    let mut reader: &[u8] = &data;
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let mut writer = RecordingVectoredWriter {
+        written: written.clone(),
+    };
+
+    // A buffer far smaller than the input forces many segment hand-offs
+    // between the reader and the writer side.
+    let n = io::CopyBuilder::new()
+        .with_capacity(4)
+        .copy(&mut reader, &mut writer)
+        .await
+        .unwrap();
+
+    assert_eq!(n, data.len() as u64);
+    assert_eq!(&*written.lock().unwrap(), &data);
+}
+
+// A writer that only ever accepts part of what it's given on its first call,
+// goes `Pending` on its second (as if the pipe were momentarily full), then
+// accepts the rest - so a test can observe the progress callback firing
+// after each of several writes that advance `amt` by different amounts,
+// with a real `Pending` in between.
+struct PartialThenPendingWriter {
+    calls: u32,
+}
+
+impl AsyncWrite for PartialThenPendingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.calls += 1;
+        match self.calls {
+            1 => Poll::Ready(Ok(5.min(buf.len()))),
+            2 => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            _ => Poll::Ready(Ok(buf.len())),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn copy_with_progress_reports_cumulative_counts_and_a_final_call_after_flush() {
+    let mut reader: &[u8] = b"hello world";
+    let mut writer = PartialThenPendingWriter { calls: 0 };
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_in_closure = seen.clone();
+
+    let n = io::copy_with_progress(&mut reader, &mut writer, move |amt| {
+        seen_in_closure.lock().unwrap().push(amt);
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(n, 11);
+    // 5 after the first (partial) write, 11 once the rest lands, and 11
+    // again for the mandatory call once the final flush completes - the
+    // `Pending` in between must not produce a spurious callback of its own.
+    assert_eq!(*seen.lock().unwrap(), vec![5, 11, 11]);
+}
@@ -0,0 +1,135 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_core::Stream;
+
+use tokio::io::{self, AsyncReadExt, ReaderStream, StreamReader};
+use tokio_stream::StreamExt;
+
+#[tokio::test]
+async fn reader_stream_yields_each_available_chunk_then_none() {
+    let reader: &[u8] = b"hello";
+    let mut stream = ReaderStream::new(reader);
+
+    // A plain `&[u8]` hands back everything it has on the first poll, so
+    // the whole input arrives as a single chunk.
+    let chunk = stream.next().await.unwrap().unwrap();
+    assert_eq!(&chunk[..], b"hello");
+
+    // Fused: once EOF is reported, every subsequent poll is `None`, not a
+    // second, empty `Ok` chunk.
+    assert!(stream.next().await.is_none());
+    assert!(stream.next().await.is_none());
+}
+
+// A reader that fails on its second `poll_read`, after having already
+// produced real data on its first - used to check that `ReaderStream`
+// surfaces that error exactly once and then fuses instead of polling a
+// reader that already failed.
+struct OkThenErr {
+    calls: u32,
+}
+
+impl io::AsyncRead for OkThenErr {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.calls += 1;
+        if self.calls == 1 {
+            buf.put_slice(b"ok");
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, "boom")))
+        }
+    }
+}
+
+#[tokio::test]
+async fn reader_stream_surfaces_error_once_then_fuses() {
+    let mut stream = ReaderStream::new(OkThenErr { calls: 0 });
+
+    let chunk = stream.next().await.unwrap().unwrap();
+    assert_eq!(&chunk[..], b"ok");
+
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(err.to_string(), "boom");
+
+    // The reader would panic or keep erroring if polled again; `stream`
+    // must not do that - it stays fused on `None` instead.
+    assert!(stream.next().await.is_none());
+}
+
+// A `Stream` of owned chunks, driven entirely by a fixed script so a test
+// can assert exactly which bytes `StreamReader` hands back on each
+// `poll_read`, including when a chunk is only partially drained.
+struct ScriptedStream {
+    items: VecDeque<Result<Bytes, io::Error>>,
+}
+
+impl Stream for ScriptedStream {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().items.pop_front())
+    }
+}
+
+#[tokio::test]
+async fn stream_reader_carries_over_a_partially_consumed_chunk() {
+    let stream = ScriptedStream {
+        items: VecDeque::from([Ok(Bytes::from_static(b"ab")), Ok(Bytes::from_static(b"cde"))]),
+    };
+    let mut reader = StreamReader::new(stream);
+
+    // Ask for just 1 byte: `StreamReader` must hand back only `a` and keep
+    // `b` buffered in the still-partially-consumed first chunk, rather than
+    // discarding it or reaching ahead into the next chunk.
+    let mut one = [0u8; 1];
+    reader.read_exact(&mut one).await.unwrap();
+    assert_eq!(&one, b"a");
+
+    // The next read drains the rest of the carried-over chunk (`b`) before
+    // the second chunk (`cde`) is ever requested from the stream.
+    let mut rest_of_first = [0u8; 1];
+    reader.read_exact(&mut rest_of_first).await.unwrap();
+    assert_eq!(&rest_of_first, b"b");
+
+    let mut second = [0u8; 3];
+    reader.read_exact(&mut second).await.unwrap();
+    assert_eq!(&second, b"cde");
+
+    // Stream is exhausted: EOF, not an error.
+    let mut trailing = [0u8; 1];
+    assert_eq!(reader.read(&mut trailing).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn stream_reader_delivers_buffered_bytes_before_surfacing_error_once() {
+    let stream = ScriptedStream {
+        items: VecDeque::from([
+            Ok(Bytes::from_static(b"he")),
+            Ok(Bytes::from_static(b"llo")),
+            Err(io::Error::new(io::ErrorKind::Other, "stream broke")),
+        ]),
+    };
+    let mut reader = StreamReader::new(stream);
+
+    // The error is the third item in the stream, but the bytes from the two
+    // `Ok` chunks ahead of it must still come through before it surfaces.
+    let mut buf = [0u8; 5];
+    reader.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"hello");
+
+    let err = reader.read(&mut buf).await.unwrap_err();
+    assert_eq!(err.to_string(), "stream broke");
+
+    // Once surfaced, the error must not repeat - further reads report EOF.
+    assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+}